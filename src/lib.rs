@@ -4,7 +4,9 @@
 
 extern crate alloc;
 
-use alloc::{collections::BTreeMap, rc::Rc};
+pub mod sync;
+
+use alloc::rc::{Rc, Weak};
 use core::{
     cell::RefCell,
     fmt::Debug,
@@ -12,17 +14,99 @@ use core::{
     task::{Context, Poll, Waker},
 };
 
+/// Describes how a call to [`Event::notify`] should count and select
+/// listeners to wake.
+///
+/// A plain `usize` (via the impl below) means "ensure at least this
+/// many listeners in total have been notified", matching `Event::notify`'s
+/// original semantics. Wrapping it with [`additional`](IntoNotification::additional)
+/// switches to "notify this many *more* listeners, on top of however many
+/// already were". [`relaxed`](IntoNotification::relaxed) documents (and, on
+/// a thread-safe `Event`, would enforce) that no extra memory-ordering
+/// fence is needed to deliver the notification; `local-event` is
+/// single-threaded so it's a no-op here, kept only so that code written
+/// against `event-listener` compiles unchanged against this crate.
+pub trait IntoNotification {
+    /// The number of listeners this notification targets.
+    fn count(&self) -> usize;
+
+    /// Whether this notification is additive (`notify_additional`-style)
+    /// rather than absolute (`notify`-style).
+    fn is_additional(&self) -> bool {
+        false
+    }
+
+    /// Wraps this notification so it targets `self.count()` listeners
+    /// *beyond* those already notified, rather than `self.count()` in total.
+    fn additional(self) -> Additional<Self>
+    where
+        Self: Sized,
+    {
+        Additional(self)
+    }
+
+    /// Wraps this notification to mark it as not requiring any extra
+    /// fencing to deliver.
+    ///
+    /// This is a no-op for `local-event`'s single-threaded `Event`, and
+    /// exists purely for source compatibility with `event-listener`.
+    fn relaxed(self) -> Relaxed<Self>
+    where
+        Self: Sized,
+    {
+        Relaxed(self)
+    }
+}
+
+impl IntoNotification for usize {
+    fn count(&self) -> usize {
+        *self
+    }
+}
+
+/// An [`IntoNotification`] that notifies `N::count()` listeners beyond those
+/// already notified. See [`IntoNotification::additional`].
+pub struct Additional<N>(N);
+
+impl<N: IntoNotification> IntoNotification for Additional<N> {
+    fn count(&self) -> usize {
+        self.0.count()
+    }
+
+    fn is_additional(&self) -> bool {
+        true
+    }
+}
+
+/// An [`IntoNotification`] that requires no extra fencing to deliver. See
+/// [`IntoNotification::relaxed`].
+pub struct Relaxed<N>(N);
+
+impl<N: IntoNotification> IntoNotification for Relaxed<N> {
+    fn count(&self) -> usize {
+        self.0.count()
+    }
+
+    fn is_additional(&self) -> bool {
+        self.0.is_additional()
+    }
+}
+
 /// A synchronization primitive for notifying tasks in a single-threaded
 /// context.
 ///
 /// This is similar to `event_listener::Event` but uses `Rc`/`RefCell` instead
 /// of thread-safe primitives, making it suitable only for single-threaded use.
+///
+/// `Event` is generic over the tag type `T` carried to woken listeners by a
+/// notification. Most users don't need a payload, so `T` defaults to `()`,
+/// making plain `Event` equivalent to `Event<()>`.
 #[derive(Clone)]
-pub struct Event {
-    inner: Rc<RefCell<Inner>>,
+pub struct Event<T = ()> {
+    inner: Rc<RefCell<Inner<T>>>,
 }
 
-impl Debug for Event {
+impl<T: Debug> Debug for Event<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let guard = self.inner.try_borrow();
         match guard {
@@ -32,25 +116,47 @@ impl Debug for Event {
     }
 }
 
-#[derive(Debug)]
-struct Inner {
-    /// List of listeners waiting for notification.
-    listeners: BTreeMap<usize, ListenerEntry>,
+/// An intrusive, doubly-linked list node holding one listener's state.
+///
+/// Listeners are always notified in FIFO order, so `Inner` keeps
+/// `next_unnotified` as a cursor at the first node that hasn't been notified
+/// yet: `notify` only ever has to walk forward from there, and dropping a
+/// notified node only ever has to hand its slot to whatever the cursor
+/// already points at, with no scan required in either case.
+struct Node<T> {
+    /// Weak to avoid a strong reference cycle with `next` on the adjacent
+    /// node; ownership flows forward from `Inner::head`.
+    prev: Option<Weak<RefCell<Node<T>>>>,
+    next: Option<Rc<RefCell<Node<T>>>>,
+    waker: Option<Waker>,
+    /// The tag delivered to this listener, if it has been notified.
+    tag: Option<T>,
+}
+
+struct Inner<T> {
+    head: Option<Rc<RefCell<Node<T>>>>,
+    tail: Option<Rc<RefCell<Node<T>>>>,
 
-    /// Counter for generating unique listener IDs.
-    next_id: usize,
+    /// The first node that hasn't been notified yet, if any.
+    next_unnotified: Option<Rc<RefCell<Node<T>>>>,
+
+    /// Total number of listeners currently registered.
+    len: usize,
 
     /// Number of notified listeners that haven't been woken yet.
     notified: usize,
 }
 
-#[derive(Debug, Default)]
-struct ListenerEntry {
-    waker: Option<Waker>,
-    notified: bool,
+impl<T: Debug> Debug for Inner<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Inner")
+            .field("len", &self.len)
+            .field("notified", &self.notified)
+            .finish()
+    }
 }
 
-impl Event {
+impl<T> Event<T> {
     /// Creates a new `Event`.
     ///
     /// # Examples
@@ -58,13 +164,15 @@ impl Event {
     /// ```
     /// use local_event::Event;
     ///
-    /// let event = Event::new();
+    /// let event: Event = Event::new();
     /// ```
     pub fn new() -> Self {
         Event {
             inner: Rc::new(RefCell::new(Inner {
-                listeners: BTreeMap::new(),
-                next_id: 0,
+                head: None,
+                tail: None,
+                next_unnotified: None,
+                len: 0,
                 notified: 0,
             })),
         }
@@ -83,69 +191,178 @@ impl Event {
     ///
     /// // Do something after the event is received.
     /// ```
-    pub fn listen(&self) -> EventListener {
+    pub fn listen(&self) -> EventListener<T> {
+        let node = Rc::new(RefCell::new(Node {
+            prev: None,
+            next: None,
+            waker: None,
+            tag: None,
+        }));
+
         let mut inner = self.inner.borrow_mut();
-        let id = inner.next_id;
-        inner.next_id += 1;
 
-        inner.listeners.insert(id, ListenerEntry::default());
+        match inner.tail.take() {
+            Some(tail) => {
+                node.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(Rc::clone(&node));
+            }
+            None => inner.head = Some(Rc::clone(&node)),
+        }
+        inner.tail = Some(Rc::clone(&node));
+
+        if inner.next_unnotified.is_none() {
+            inner.next_unnotified = Some(Rc::clone(&node));
+        }
+        inner.len += 1;
 
         EventListener {
             event: Rc::clone(&self.inner),
-            id,
+            node,
         }
     }
 
-    /// Notifies a number of active listeners.
+    /// Returns the total number of active listeners, notified or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use local_event::Event;
+    ///
+    /// let event: Event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// assert_eq!(event.total_listeners(), 1);
+    /// ```
+    pub fn total_listeners(&self) -> usize {
+        self.inner.borrow().len
+    }
+
+    /// Returns the number of listeners that have been notified but haven't
+    /// been *polled past* their notification yet.
+    ///
+    /// This counts towards the budget consumed by absolute `notify(n)` calls,
+    /// so it goes back down once a notified listener is polled to completion,
+    /// not just when it's removed from the list. Dropping a notified listener
+    /// before polling it hands its notification to the next waiting listener,
+    /// so the count only goes down if no listener is left waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use local_event::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// event.notify(1);
+    /// assert_eq!(event.notified_count(), 1);
+    /// ```
+    pub fn notified_count(&self) -> usize {
+        self.inner.borrow().notified
+    }
+
+    /// Notifies a number of active listeners, delivering a tag produced by
+    /// `tag` to each one.
     ///
     /// The number of notified listeners is determined by `n`:
     /// - If `n` is `usize::MAX`, all active listeners are notified.
     /// - Otherwise, `n` active listeners are notified.
     ///
+    /// For a plain `usize`, listeners already notified count towards `n`, so
+    /// only the shortfall is notified; wrapped with
+    /// [`IntoNotification::additional`], `n` more listeners are notified.
+    ///
+    /// `tag` is only called once per listener that actually gets notified, so
+    /// callers can avoid cloning a value when fewer than `n` listeners exist.
+    ///
     /// # Examples
     ///
     /// ```
-    /// use local_event::Event;
+    /// use local_event::{Event, IntoNotification};
     ///
     /// let event = Event::new();
     ///
-    /// // Notify all listeners.
-    /// event.notify(usize::MAX);
+    /// // Ensure at least 5 listeners in total are notified, tagging each new one with 1.
+    /// event.notify_with(5, || 1);
     ///
-    /// // Notify exactly 5 listeners.
-    /// event.notify(5);
+    /// // Notify 2 *additional* listeners, mirroring `event-listener`.
+    /// event.notify_with(2.additional(), || 1);
     /// ```
-    pub fn notify(&self, n: usize) {
+    pub fn notify_with(&self, notify: impl IntoNotification, tag: impl Fn() -> T) {
         let mut inner = self.inner.borrow_mut();
 
+        let n = notify.count();
+        let remaining = inner.len.saturating_sub(inner.notified);
         let count = if n == usize::MAX {
-            inner.listeners.len()
+            remaining
+        } else if notify.is_additional() {
+            n.min(remaining)
         } else {
             n.saturating_sub(inner.notified)
         };
 
+        // `next_unnotified` is always the first not-yet-notified node, so
+        // notifying is just advancing the cursor `count` steps instead of
+        // rescanning the whole list from the front.
         let mut notified = 0;
-        for entry in inner.listeners.values_mut() {
-            if notified >= count {
+        while notified < count {
+            let Some(node) = inner.next_unnotified.clone() else {
                 break;
-            }
-            if entry.notified {
-                continue;
-            }
-            entry.notified = true;
-            if let Some(waker) = entry.waker.take() {
+            };
+
+            let mut node_mut = node.borrow_mut();
+            node_mut.tag = Some(tag());
+            if let Some(waker) = node_mut.waker.take() {
                 waker.wake();
             }
+            let next = node_mut.next.clone();
+            drop(node_mut);
+
+            inner.next_unnotified = next;
             notified += 1;
         }
 
         inner.notified += notified;
     }
+}
+
+impl Event<()> {
+    /// Notifies a number of listeners, as described by `notify`.
+    ///
+    /// A plain `usize` ensures that at least `n` listeners total have been
+    /// notified (the behavior of the original `notify`); wrap it with [`IntoNotification::additional`] to instead notify
+    /// `n` *more* listeners regardless of how many were already notified.
+    /// `usize::MAX` notifies every active listener either way.
+    ///
+    /// Only available on the plain `Event` (i.e. `Event<()>`) alias, since
+    /// there's no general way to make up a value of an arbitrary tag type `T`.
+    /// Use [`Event::notify_with`] on a payload-carrying `Event<T>` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use local_event::{Event, IntoNotification};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // Notify all listeners.
+    /// event.notify(usize::MAX);
+    ///
+    /// // Ensure at least 5 listeners in total are notified.
+    /// event.notify(5);
+    ///
+    /// // Notify 2 listeners beyond those already notified.
+    /// event.notify(2.additional());
+    /// ```
+    pub fn notify(&self, notify: impl IntoNotification) {
+        self.notify_with(notify, || ());
+    }
 
     /// Notifies a number of active and still waiting listeners.
     ///
     /// Unlike `notify()`, this method only notifies listeners that haven't been
-    /// notified yet and are still registered.
+    /// notified yet and are still registered. Equivalent to
+    /// `self.notify(n.additional())`.
     ///
     /// # Examples
     ///
@@ -156,30 +373,7 @@ impl Event {
     /// event.notify_additional(2);
     /// ```
     pub fn notify_additional(&self, n: usize) {
-        let mut inner = self.inner.borrow_mut();
-
-        let count = if n == usize::MAX {
-            inner.listeners.len()
-        } else {
-            n.min(inner.listeners.len())
-        };
-
-        let mut notified = 0;
-        for entry in inner.listeners.values_mut() {
-            if notified >= count {
-                break;
-            }
-            if entry.notified {
-                continue;
-            }
-            entry.notified = true;
-            if let Some(waker) = entry.waker.take() {
-                waker.wake();
-            }
-            notified += 1;
-        }
-
-        inner.notified += notified;
+        self.notify(n.additional());
     }
 
     /// Notifies all active listeners.
@@ -206,7 +400,7 @@ impl Event {
     }
 }
 
-impl Default for Event {
+impl<T> Default for Event<T> {
     fn default() -> Self {
         Self::new()
     }
@@ -214,13 +408,14 @@ impl Default for Event {
 
 /// A guard waiting for a notification from an [`Event`].
 ///
-/// This listener can be polled or `await`-ed as a [`Future`].
-pub struct EventListener {
-    event: Rc<RefCell<Inner>>,
-    id: usize,
+/// This listener can be polled or `await`-ed as a [`Future`](core::future::Future),
+/// resolving to the tag delivered by the notification that woke it.
+pub struct EventListener<T = ()> {
+    event: Rc<RefCell<Inner<T>>>,
+    node: Rc<RefCell<Node<T>>>,
 }
 
-impl EventListener {
+impl<T> EventListener<T> {
     /// Returns `true` if this listener has been notified.
     ///
     /// # Examples
@@ -236,60 +431,93 @@ impl EventListener {
     /// assert!(listener.is_notified());
     /// ```
     pub fn is_notified(&self) -> bool {
-        self.event
-            .borrow()
-            .listeners
-            .get(&self.id)
-            .map(|e| e.notified)
-            .unwrap_or(false)
+        self.node.borrow().tag.is_some()
     }
 }
 
-impl Drop for EventListener {
+impl<T> Drop for EventListener<T> {
     fn drop(&mut self) {
         let mut inner = self.event.borrow_mut();
+        let mut node = self.node.borrow_mut();
+
+        // Unlink this node from the list in O(1).
+        let prev = node.prev.as_ref().and_then(Weak::upgrade);
+        let next = node.next.clone();
+        match (prev, next) {
+            (Some(prev), Some(next)) => {
+                prev.borrow_mut().next = Some(Rc::clone(&next));
+                next.borrow_mut().prev = Some(Rc::downgrade(&prev));
+            }
+            (Some(prev), None) => {
+                prev.borrow_mut().next = None;
+                inner.tail = Some(prev);
+            }
+            (None, Some(next)) => {
+                next.borrow_mut().prev = None;
+                inner.head = Some(next);
+            }
+            (None, None) => {
+                inner.head = None;
+                inner.tail = None;
+            }
+        }
+        inner.len -= 1;
+
+        // If this was the cursor, the node right after it (if any) becomes
+        // the new first not-yet-notified node.
+        if inner
+            .next_unnotified
+            .as_ref()
+            .is_some_and(|cursor| Rc::ptr_eq(cursor, &self.node))
+        {
+            inner.next_unnotified = node.next.clone();
+        }
 
-        // Find and remove this listener
-        let Some(entry) = inner.listeners.remove(&self.id) else {
+        let Some(tag) = node.tag.take() else {
             return;
         };
+        drop(node);
 
-        if !entry.notified || inner.notified == 0 {
+        if inner.notified == 0 {
             return;
         }
-
         inner.notified -= 1;
 
-        let Some(next) = inner.listeners.values_mut().find(|e| !e.notified) else {
+        // Hand this listener's tag off to the next listener still waiting.
+        let Some(next) = inner.next_unnotified.clone() else {
             return;
         };
-
-        next.notified = true;
-
-        if let Some(waker) = next.waker.take() {
+        let mut next_mut = next.borrow_mut();
+        next_mut.tag = Some(tag);
+        if let Some(waker) = next_mut.waker.take() {
             waker.wake();
         }
+        let after_next = next_mut.next.clone();
+        drop(next_mut);
 
+        inner.next_unnotified = after_next;
         inner.notified += 1;
     }
 }
 
-impl core::future::Future for EventListener {
-    type Output = ();
+impl<T> core::future::Future for EventListener<T> {
+    type Output = T;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let mut inner = self.event.borrow_mut();
-
-        let Some(entry) = inner.listeners.get_mut(&self.id) else {
-            unreachable!("Entry shouldn't be removed")
-        };
-
-        if entry.notified {
-            return Poll::Ready(());
+        let mut node = self.node.borrow_mut();
+
+        if let Some(tag) = node.tag.take() {
+            drop(node);
+            // This listener's notification has now been delivered, so it no
+            // longer counts towards `notified`. Without this, `Drop` sees an
+            // already-taken tag and skips its own decrement, permanently
+            // inflating the counter.
+            self.event.borrow_mut().notified -= 1;
+            return Poll::Ready(tag);
         }
 
         // Store the waker for later notification
-        entry.waker = Some(cx.waker().clone());
+        node.waker = Some(cx.waker().clone());
 
         Poll::Pending
     }
@@ -311,7 +539,7 @@ impl core::future::Future for EventListener {
 /// ```
 /// use local_event::{Event, listener};
 ///
-/// let event = Event::new();
+/// let event: Event = Event::new();
 /// listener!(event => listener);
 /// // equivalent to: let mut listener = event.listen();
 /// ```
@@ -421,4 +649,108 @@ mod tests {
 
         listener.await
     }
+
+    #[test]
+    fn test_total_listeners() {
+        let event: Event = Event::new();
+        assert_eq!(event.total_listeners(), 0);
+
+        let listener1 = event.listen();
+        let listener2 = event.listen();
+        assert_eq!(event.total_listeners(), 2);
+
+        drop(listener1);
+        assert_eq!(event.total_listeners(), 1);
+
+        drop(listener2);
+        assert_eq!(event.total_listeners(), 0);
+    }
+
+    #[test]
+    fn test_notified_count() {
+        let event = Event::new();
+        let listener1 = event.listen();
+        let listener2 = event.listen();
+        let _listener3 = event.listen();
+
+        assert_eq!(event.notified_count(), 0);
+
+        event.notify(2);
+        assert_eq!(event.notified_count(), 2);
+
+        // Dropping a notified listener hands its slot off to the next
+        // waiting one, so the notified count is unchanged.
+        drop(listener1);
+        assert_eq!(event.notified_count(), 2);
+
+        // No more waiting listeners left to hand off to.
+        drop(listener2);
+        assert_eq!(event.notified_count(), 1);
+    }
+
+    #[pollster::test]
+    async fn test_notified_count_after_poll() {
+        let event = Event::new();
+        let listener = event.listen();
+
+        event.notify(1);
+        assert_eq!(event.notified_count(), 1);
+
+        // Polling a notified listener to completion delivers its
+        // notification, so it should stop counting towards `notified_count`
+        // just like dropping it would.
+        listener.await;
+        assert_eq!(event.notified_count(), 0);
+        assert_eq!(event.total_listeners(), 0);
+
+        // And the freed-up notification budget should be reusable.
+        let listener = event.listen();
+        event.notify(1);
+        assert!(listener.is_notified());
+    }
+
+    #[pollster::test]
+    async fn test_notify_with_tag() {
+        let event: Event<u32> = Event::new();
+        let listener = event.listen();
+
+        event.notify_with(1, || 42);
+
+        assert_eq!(listener.await, 42);
+    }
+
+    #[test]
+    fn test_into_notification_additional() {
+        let event = Event::new();
+        let listener1 = event.listen();
+        let listener2 = event.listen();
+        let listener3 = event.listen();
+        let listener4 = event.listen();
+
+        event.notify(2);
+
+        assert!(listener1.is_notified());
+        assert!(listener2.is_notified());
+        assert!(!listener3.is_notified());
+        assert!(!listener4.is_notified());
+
+        event.notify(2.additional());
+
+        assert!(listener1.is_notified());
+        assert!(listener2.is_notified());
+        assert!(listener3.is_notified());
+        assert!(listener4.is_notified());
+    }
+
+    #[test]
+    fn test_into_notification_relaxed() {
+        let event = Event::new();
+        let listener1 = event.listen();
+        let listener2 = event.listen();
+
+        event.notify(1.additional().relaxed());
+
+        assert!(listener1.is_notified());
+        assert!(!listener2.is_notified());
+    }
 }
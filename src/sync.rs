@@ -0,0 +1,370 @@
+//! Single-threaded async synchronization primitives built on top of
+//! [`Event`](crate::Event).
+//!
+//! These follow the eventcount pattern used by `event-listener`'s own mutex
+//! example: a plain [`Cell`] holds the actual state, and an [`Event`] is used
+//! purely to park and wake waiters. Because the crate is single-threaded,
+//! there are no atomics anywhere in this module.
+
+use core::{
+    cell::{Cell, RefCell, RefMut},
+    ops::{Deref, DerefMut},
+};
+
+use crate::Event;
+
+/// An async mutex for single-threaded executors.
+///
+/// Unlike `std::sync::Mutex`, [`Mutex::lock`] is an `async fn`: instead of
+/// blocking the thread, a task that finds the mutex locked registers an
+/// [`Event`] listener and yields until the current holder unlocks it.
+pub struct Mutex<T> {
+    locked: Cell<bool>,
+    event: Event,
+    value: RefCell<T>,
+}
+
+impl<T> Mutex<T> {
+    /// Creates a new, unlocked mutex wrapping `value`.
+    pub fn new(value: T) -> Self {
+        Mutex {
+            locked: Cell::new(false),
+            event: Event::new(),
+            value: RefCell::new(value),
+        }
+    }
+
+    /// Tries to acquire the lock without waiting, returning `None` if it's
+    /// already held.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        if self.locked.replace(true) {
+            return None;
+        }
+
+        Some(MutexGuard {
+            mutex: self,
+            value: self.value.borrow_mut(),
+        })
+    }
+
+    /// Acquires the lock, waiting until it's available.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use local_event::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// *mutex.lock().await += 1;
+    /// ```
+    pub async fn lock(&self) -> MutexGuard<'_, T> {
+        loop {
+            if let Some(guard) = self.try_lock() {
+                return guard;
+            }
+
+            let listener = self.event.listen();
+
+            // Check again before waiting: the lock may have been released
+            // between the `try_lock` above and registering the listener,
+            // and without this check we'd miss that notification.
+            if let Some(guard) = self.try_lock() {
+                return guard;
+            }
+
+            listener.await;
+        }
+    }
+
+    fn unlock(&self) {
+        self.locked.set(false);
+        self.event.notify_additional(1);
+    }
+}
+
+/// A guard granting exclusive access to a [`Mutex`]'s value, returned by
+/// [`Mutex::lock`] and [`Mutex::try_lock`].
+///
+/// Dropping the guard releases the lock and wakes the next waiting task, if
+/// any.
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+    value: RefMut<'a, T>,
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
+
+/// A counting semaphore for single-threaded executors.
+///
+/// A fixed number of permits are handed out by [`Semaphore::acquire`]; when
+/// none are left, callers wait for one to be released.
+pub struct Semaphore {
+    permits: Cell<usize>,
+    event: Event,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with `permits` permits available.
+    pub fn new(permits: usize) -> Self {
+        Semaphore {
+            permits: Cell::new(permits),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns the number of permits currently available.
+    pub fn available_permits(&self) -> usize {
+        self.permits.get()
+    }
+
+    /// Tries to acquire a permit without waiting, returning `None` if none
+    /// are available.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let permits = self.permits.get();
+        if permits == 0 {
+            return None;
+        }
+
+        self.permits.set(permits - 1);
+        Some(SemaphorePermit { semaphore: self })
+    }
+
+    /// Acquires a permit, waiting until one is available.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use local_event::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.acquire().await;
+    /// ```
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        loop {
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+
+            let listener = self.event.listen();
+
+            // See `Mutex::lock` for why this second check is needed.
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+
+            listener.await;
+        }
+    }
+
+    fn release(&self) {
+        self.permits.set(self.permits.get() + 1);
+        self.event.notify_additional(1);
+    }
+}
+
+/// A permit obtained from a [`Semaphore`], returned by
+/// [`Semaphore::acquire`] and [`Semaphore::try_acquire`].
+///
+/// Dropping the permit returns it to the semaphore and wakes the next
+/// waiting task, if any.
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
+
+/// A single-use barrier that releases all waiters once a fixed number of
+/// parties have called [`WaitGroup::done`].
+///
+/// This is the single-threaded async equivalent of a `sync::WaitGroup`: tasks
+/// call [`WaitGroup::wait`] to block until the counter reaches zero, which
+/// happens once as many parties have reported in via `done` as were
+/// registered via `new`/[`WaitGroup::add`].
+pub struct WaitGroup {
+    count: Cell<usize>,
+    event: Event,
+}
+
+impl WaitGroup {
+    /// Creates a new wait group for `count` parties.
+    pub fn new(count: usize) -> Self {
+        WaitGroup {
+            count: Cell::new(count),
+            event: Event::new(),
+        }
+    }
+
+    /// Registers `n` additional parties that [`WaitGroup::wait`] should wait
+    /// for.
+    pub fn add(&self, n: usize) {
+        self.count.set(self.count.get() + n);
+    }
+
+    /// Reports that one party has finished.
+    ///
+    /// Once every registered party has called `done`, all current and future
+    /// callers of [`WaitGroup::wait`] are woken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called more times than parties were registered.
+    pub fn done(&self) {
+        let count = self
+            .count
+            .get()
+            .checked_sub(1)
+            .expect("WaitGroup::done called more times than parties were registered");
+
+        self.count.set(count);
+        if count == 0 {
+            self.event.notify_all();
+        }
+    }
+
+    /// Waits until every registered party has called [`WaitGroup::done`].
+    ///
+    /// Returns immediately if that has already happened.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use local_event::sync::WaitGroup;
+    ///
+    /// let wg = WaitGroup::new(1);
+    /// wg.done();
+    /// wg.wait().await;
+    /// ```
+    pub async fn wait(&self) {
+        loop {
+            if self.count.get() == 0 {
+                return;
+            }
+
+            let listener = self.event.listen();
+
+            // See `Mutex::lock` for why this second check is needed.
+            if self.count.get() == 0 {
+                return;
+            }
+
+            listener.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{boxed::Box, rc::Rc};
+    use core::{
+        future::Future,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    /// A `Waker` that records whether it was woken, for manually driving two
+    /// competing futures without a real executor.
+    fn flag_waker() -> (Waker, Rc<Cell<bool>>) {
+        fn clone(data: *const ()) -> RawWaker {
+            unsafe { Rc::increment_strong_count(data as *const Cell<bool>) };
+            RawWaker::new(data, &VTABLE)
+        }
+        fn wake(data: *const ()) {
+            wake_by_ref(data);
+            drop(unsafe { Rc::from_raw(data as *const Cell<bool>) });
+        }
+        fn wake_by_ref(data: *const ()) {
+            unsafe { &*(data as *const Cell<bool>) }.set(true);
+        }
+        fn drop_raw(data: *const ()) {
+            drop(unsafe { Rc::from_raw(data as *const Cell<bool>) });
+        }
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_raw);
+
+        let flag = Rc::new(Cell::new(false));
+        let data = Rc::into_raw(Rc::clone(&flag)) as *const ();
+        (unsafe { Waker::from_raw(RawWaker::new(data, &VTABLE)) }, flag)
+    }
+
+    #[test]
+    fn test_mutex_two_waiters_contend() {
+        let mutex = Mutex::new(0);
+
+        // Hold the lock so both `lock()` calls below have to park.
+        let guard = mutex.try_lock().unwrap();
+
+        let mut fut1 = Box::pin(mutex.lock());
+        let (waker1, woken1) = flag_waker();
+        let mut cx1 = Context::from_waker(&waker1);
+        assert!(matches!(fut1.as_mut().poll(&mut cx1), Poll::Pending));
+
+        let mut fut2 = Box::pin(mutex.lock());
+        let (waker2, woken2) = flag_waker();
+        let mut cx2 = Context::from_waker(&waker2);
+        assert!(matches!(fut2.as_mut().poll(&mut cx2), Poll::Pending));
+
+        drop(guard);
+        assert!(woken1.get(), "releasing the lock should wake the first waiter");
+        assert!(!woken2.get(), "the second waiter should still be parked");
+
+        let Poll::Ready(guard1) = fut1.as_mut().poll(&mut cx1) else {
+            panic!("first waiter should acquire the lock after release");
+        };
+        assert!(!woken2.get());
+
+        drop(guard1);
+        assert!(
+            woken2.get(),
+            "releasing the lock again should wake the second waiter"
+        );
+
+        let Poll::Ready(_guard2) = fut2.as_mut().poll(&mut cx2) else {
+            panic!("second waiter should acquire the lock after the first releases");
+        };
+    }
+
+    #[pollster::test]
+    async fn test_semaphore_basic() {
+        let semaphore = Semaphore::new(1);
+        assert_eq!(semaphore.available_permits(), 1);
+
+        let permit = semaphore.acquire().await;
+        assert_eq!(semaphore.available_permits(), 0);
+
+        drop(permit);
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+
+    #[pollster::test]
+    async fn test_wait_group() {
+        let wg = WaitGroup::new(2);
+
+        wg.done();
+        wg.done();
+
+        // Both parties are done, so this resolves immediately.
+        wg.wait().await;
+    }
+}